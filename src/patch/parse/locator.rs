@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: GPL-2.0-only
+
+//! Parser for patch locators: a reference to a single patch, optionally
+//! offset from a base patch by its position on the stack, git-revspec
+//! style.
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{map, opt},
+    multi::many0,
+    sequence::pair,
+    IResult,
+};
+use thiserror::Error;
+
+use crate::patchname::PatchName;
+
+use super::name::{name, resolve_name, ResolveError};
+use super::numbers::numbers;
+use super::Sign;
+
+/// A parsed locator: a base patch name plus the net signed offset
+/// accumulated from any `~`/`+` suffix operators.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Locator<'i> {
+    base: &'i str,
+    offset: i64,
+}
+
+/// Parse a raw locator: a base name followed by any number of `~`/`+`
+/// suffix operators, e.g. `patch`, `patch~2`, `patch~-1`, `patch+1~1`.
+pub(super) fn locator(input: &str) -> IResult<&str, Locator<'_>> {
+    let (input, base) = name(input)?;
+    let (input, ops) = many0(offset_op)(input)?;
+    Ok((
+        input,
+        Locator {
+            base,
+            offset: ops.into_iter().sum(),
+        },
+    ))
+}
+
+/// Parse a single `~` or `+` suffix operator into its net signed offset.
+///
+/// `~` moves down the stack by default, `+` moves up; an explicit sign on
+/// the count (e.g. the `-1` in `patch~-1`) flips that default, so `~-1`
+/// and `+1` both mean "one patch up".
+fn offset_op(input: &str) -> IResult<&str, i64> {
+    alt((
+        map(pair(tag("~"), opt(signed_count)), |(_, count)| {
+            -count.unwrap_or(1)
+        }),
+        map(pair(tag("+"), opt(signed_count)), |(_, count)| {
+            count.unwrap_or(1)
+        }),
+    ))(input)
+}
+
+/// Parse an explicit signed count following an operator, e.g. the `-1` in
+/// `patch~-1`. An un-prefixed count is positive.
+fn signed_count(input: &str) -> IResult<&str, i64> {
+    let (input, sign) = opt(alt((
+        map(tag("+"), |_| Sign::Plus),
+        map(tag("-"), |_| Sign::Minus),
+    )))(input)?;
+    let (input, value) = numbers(input)?;
+    let value = value as i64;
+    Ok((
+        input,
+        match sign {
+            Some(Sign::Minus) => -value,
+            _ => value,
+        },
+    ))
+}
+
+/// An error resolving a [`Locator`] against the patch stack.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(super) enum LocatorError {
+    #[error(transparent)]
+    Resolve(#[from] ResolveError),
+
+    /// The offset moved past the top or bottom of the applied stack.
+    #[error("offset from `{base}` is out of range")]
+    OutOfRange { base: PatchName },
+}
+
+/// Resolve a parsed [`Locator`] against `applied`, the ordered list of
+/// currently applied patches.
+pub(super) fn resolve_locator(
+    parsed: &Locator<'_>,
+    applied: &[PatchName],
+) -> Result<PatchName, LocatorError> {
+    let base = resolve_name(parsed.base, applied)?;
+    let base_index = applied
+        .iter()
+        .position(|p| p.as_ref() == base.as_ref())
+        .expect("resolve_name only returns patches present in `applied`");
+
+    let target = base_index as i64 + parsed.offset;
+    if target < 0 || target as usize >= applied.len() {
+        return Err(LocatorError::OutOfRange { base });
+    }
+    Ok(applied[target as usize].clone())
+}
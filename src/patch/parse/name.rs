@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: GPL-2.0-only
+
+//! Parser and resolver for patch name tokens.
+
+use nom::{error::ErrorKind, IResult};
+use thiserror::Error;
+
+use crate::patchname::PatchName;
+
+/// Parse a raw patch name token.
+///
+/// This only recognizes the token's character shape; it says nothing about
+/// whether the token actually names a patch on the stack. Use
+/// [`resolve_name`] for that.
+///
+/// A single `.` is a valid name character (patch names like `v1.2` are
+/// common), but two consecutive dots are not: `..` is reserved for the
+/// range separator, so the token stops just before it rather than
+/// swallowing it.
+pub(super) fn name(input: &str) -> IResult<&str, &str> {
+    let mut end = 0;
+    let mut chars = input.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if !is_name_char(c) {
+            break;
+        }
+        if c == '.' && chars.peek().map(|&(_, next)| next) == Some('.') {
+            break;
+        }
+        end = i + c.len_utf8();
+    }
+    if end == 0 {
+        return Err(nom::Err::Error(nom::error::Error::new(input, ErrorKind::TakeWhile1)));
+    }
+    Ok((&input[end..], &input[..end]))
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/')
+}
+
+/// An error resolving a raw name token against the stack's patch names.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(super) enum ResolveError {
+    /// More than one patch matched `token` at the first tier that matched
+    /// anything at all.
+    #[error("ambiguous patch name `{token}`: could be {candidates}")]
+    Ambiguous { token: String, candidates: String },
+
+    /// No patch matched `token` at any tier.
+    #[error("unknown patch `{token}`")]
+    Unknown { token: String },
+}
+
+fn ambiguous(token: &str, candidates: &[&PatchName]) -> ResolveError {
+    let candidates = candidates
+        .iter()
+        .map(|p| p.as_ref())
+        .collect::<Vec<_>>()
+        .join(", ");
+    ResolveError::Ambiguous {
+        token: token.to_string(),
+        candidates,
+    }
+}
+
+/// Resolve a raw name token to one of `patches`.
+///
+/// Matching is attempted in precedence order: exact match, unique
+/// case-sensitive prefix, unique case-insensitive exact match, unique
+/// case-insensitive prefix. Ambiguity is reported from the first tier that
+/// matched anything, even if a later tier would have matched uniquely.
+pub(super) fn resolve_name(
+    token: &str,
+    patches: &[PatchName],
+) -> Result<PatchName, ResolveError> {
+    let exact: Vec<&PatchName> = patches.iter().filter(|p| p.as_ref() == token).collect();
+    match exact.len() {
+        1 => return Ok(exact[0].clone()),
+        n if n > 1 => return Err(ambiguous(token, &exact)),
+        _ => {}
+    }
+
+    let prefix: Vec<&PatchName> = patches
+        .iter()
+        .filter(|p| p.as_ref().starts_with(token))
+        .collect();
+    if !prefix.is_empty() {
+        return if prefix.len() == 1 {
+            Ok(prefix[0].clone())
+        } else {
+            Err(ambiguous(token, &prefix))
+        };
+    }
+
+    let lower = token.to_lowercase();
+    let ci_exact: Vec<&PatchName> = patches
+        .iter()
+        .filter(|p| p.as_ref().to_lowercase() == lower)
+        .collect();
+    if !ci_exact.is_empty() {
+        return if ci_exact.len() == 1 {
+            Ok(ci_exact[0].clone())
+        } else {
+            Err(ambiguous(token, &ci_exact))
+        };
+    }
+
+    let ci_prefix: Vec<&PatchName> = patches
+        .iter()
+        .filter(|p| p.as_ref().to_lowercase().starts_with(&lower))
+        .collect();
+    match ci_prefix.len() {
+        0 => Err(ResolveError::Unknown {
+            token: token.to_string(),
+        }),
+        1 => Ok(ci_prefix[0].clone()),
+        _ => Err(ambiguous(token, &ci_prefix)),
+    }
+}
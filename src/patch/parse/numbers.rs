@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: GPL-2.0-only
+
+//! Parsers for the numeric literals used by offset operators.
+
+use nom::{character::complete::digit1, combinator::map_res, IResult};
+
+/// Parse a run of ASCII digits as an unsigned integer.
+pub(super) fn numbers(input: &str) -> IResult<&str, usize> {
+    map_res(digit1, str::parse)(input)
+}
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: GPL-2.0-only
+
+//! Parser for patch ranges: a contiguous span of patches on the stack.
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{map, opt},
+    sequence::{preceded, separated_pair, terminated},
+    IResult,
+};
+use thiserror::Error;
+
+use crate::patchname::PatchName;
+
+use super::name::{name, resolve_name, ResolveError};
+
+/// A parsed patch range, with endpoints as unresolved name tokens.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum Range<'i> {
+    /// `a..b`: from `a` through `b`, inclusive.
+    Closed(&'i str, &'i str),
+    /// `a..`: from `a` through the top of the applied stack.
+    FromOpen(&'i str),
+    /// `..b`: from the base of the stack through `b`.
+    ToOpen(&'i str),
+    /// `a+`: `a` and every applied patch after it.
+    AndLater(&'i str),
+}
+
+/// Parse a raw range expression: `a..b`, `a..`, `..b`, or `a+`.
+pub(super) fn range(input: &str) -> IResult<&str, Range<'_>> {
+    alt((
+        map(preceded(tag(".."), name), Range::ToOpen),
+        map(terminated(name, tag("+")), Range::AndLater),
+        map(separated_pair(name, tag(".."), opt(name)), |(a, b)| {
+            match b {
+                Some(b) => Range::Closed(a, b),
+                None => Range::FromOpen(a),
+            }
+        }),
+    ))(input)
+}
+
+/// An error resolving a [`Range`] against the patch stack.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(super) enum RangeError {
+    #[error(transparent)]
+    Resolve(#[from] ResolveError),
+
+    /// An endpoint resolved to a patch that isn't applied, so "before" or
+    /// "after" in the applied order is meaningless.
+    #[error("patch `{0}` is not applied")]
+    NotApplied(PatchName),
+
+    /// The first endpoint of a `Closed` range sits above the second in
+    /// applied order.
+    #[error("range `{from}..{to}` is inverted")]
+    Inverted { from: PatchName, to: PatchName },
+}
+
+/// Expand a [`Range`] into the concrete, ordered slice of patch names it
+/// covers, given `applied`, the ordered list of currently applied patches.
+pub(super) fn resolve_range(
+    parsed: &Range<'_>,
+    applied: &[PatchName],
+) -> Result<Vec<PatchName>, RangeError> {
+    let index_of = |p: &PatchName| -> Result<usize, RangeError> {
+        applied
+            .iter()
+            .position(|a| a.as_ref() == p.as_ref())
+            .ok_or_else(|| RangeError::NotApplied(p.clone()))
+    };
+
+    match *parsed {
+        Range::Closed(a, b) => {
+            let a = resolve_name(a, applied)?;
+            let b = resolve_name(b, applied)?;
+            let (lo, hi) = (index_of(&a)?, index_of(&b)?);
+            if lo > hi {
+                return Err(RangeError::Inverted { from: a, to: b });
+            }
+            Ok(applied[lo..=hi].to_vec())
+        }
+        Range::FromOpen(a) | Range::AndLater(a) => {
+            let a = resolve_name(a, applied)?;
+            let lo = index_of(&a)?;
+            Ok(applied[lo..].to_vec())
+        }
+        Range::ToOpen(b) => {
+            let b = resolve_name(b, applied)?;
+            let hi = index_of(&b)?;
+            Ok(applied[..=hi].to_vec())
+        }
+    }
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: GPL-2.0-only
+
+//! Parser for revision specifications: a reference to the commit recorded
+//! by a single patch.
+
+use nom::IResult;
+
+use crate::patchname::PatchName;
+
+use super::name::{name, resolve_name, ResolveError};
+
+/// A resolved revision: the commit associated with a single patch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Revision(pub(super) PatchName);
+
+/// Parse a raw revision token.
+pub(super) fn revision(input: &str) -> IResult<&str, &str> {
+    name(input)
+}
+
+/// Resolve a parsed revision token against `applied`, the ordered list of
+/// currently applied patches.
+pub(super) fn resolve_revision(
+    token: &str,
+    applied: &[PatchName],
+) -> Result<Revision, ResolveError> {
+    resolve_name(token, applied).map(Revision)
+}
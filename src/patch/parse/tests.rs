@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: GPL-2.0-only
+
+//! Tests for patch and revision specification parsers.
+
+use crate::patchname::PatchName;
+
+use super::locator::{locator, resolve_locator, LocatorError};
+use super::name::{name, resolve_name, ResolveError};
+use super::range::{range, resolve_range, Range, RangeError};
+
+fn patches(names: &[&str]) -> Vec<PatchName> {
+    names.iter().map(|n| n.parse().unwrap()).collect()
+}
+
+fn names(patches: &[PatchName]) -> Vec<&str> {
+    patches.iter().map(|p| p.as_ref()).collect()
+}
+
+#[test]
+fn resolve_name_exact_match() {
+    let stack = patches(&["feature-a", "feature-b"]);
+    assert_eq!(
+        resolve_name("feature-a", &stack).unwrap().as_ref(),
+        "feature-a"
+    );
+}
+
+#[test]
+fn resolve_name_unique_prefix() {
+    let stack = patches(&["feature-rework-auth", "bugfix-login"]);
+    assert_eq!(
+        resolve_name("feat", &stack).unwrap().as_ref(),
+        "feature-rework-auth"
+    );
+}
+
+#[test]
+fn resolve_name_ambiguous_prefix() {
+    let stack = patches(&["feature-a", "feature-b"]);
+    assert_eq!(
+        resolve_name("feature", &stack).unwrap_err(),
+        ResolveError::Ambiguous {
+            token: "feature".to_string(),
+            candidates: "feature-a, feature-b".to_string(),
+        }
+    );
+}
+
+#[test]
+fn resolve_name_case_insensitive_exact() {
+    let stack = patches(&["Feature-A"]);
+    assert_eq!(
+        resolve_name("feature-a", &stack).unwrap().as_ref(),
+        "Feature-A"
+    );
+}
+
+#[test]
+fn resolve_name_case_insensitive_prefix() {
+    let stack = patches(&["Feature-Rework-Auth"]);
+    assert_eq!(
+        resolve_name("feat", &stack).unwrap().as_ref(),
+        "Feature-Rework-Auth"
+    );
+}
+
+#[test]
+fn resolve_name_ambiguity_from_earliest_tier() {
+    // "feature" is an ambiguous case-sensitive prefix of "feature-a" and
+    // "feature-b" even though it is a unique case-insensitive exact match
+    // for "FEATURE"; the case-sensitive prefix tier must win and report
+    // ambiguous.
+    let stack = patches(&["feature-a", "feature-b", "FEATURE"]);
+    assert!(matches!(
+        resolve_name("feature", &stack),
+        Err(ResolveError::Ambiguous { .. })
+    ));
+}
+
+#[test]
+fn resolve_name_unknown() {
+    let stack = patches(&["feature-a"]);
+    assert_eq!(
+        resolve_name("nope", &stack).unwrap_err(),
+        ResolveError::Unknown {
+            token: "nope".to_string()
+        }
+    );
+}
+
+#[test]
+fn name_stops_before_double_dot() {
+    let (rest, matched) = name("a..b").unwrap();
+    assert_eq!(matched, "a");
+    assert_eq!(rest, "..b");
+}
+
+#[test]
+fn name_allows_a_single_dot() {
+    let (rest, matched) = name("v1.2..v1.3").unwrap();
+    assert_eq!(matched, "v1.2");
+    assert_eq!(rest, "..v1.3");
+}
+
+#[test]
+fn range_parses_closed() {
+    let (rest, parsed) = range("a..c").unwrap();
+    assert_eq!(rest, "");
+    assert_eq!(parsed, Range::Closed("a", "c"));
+}
+
+#[test]
+fn range_parses_closed_with_dotted_names() {
+    let (rest, parsed) = range("v1.2..v1.3").unwrap();
+    assert_eq!(rest, "");
+    assert_eq!(parsed, Range::Closed("v1.2", "v1.3"));
+}
+
+#[test]
+fn range_parses_from_open() {
+    let (rest, parsed) = range("b..").unwrap();
+    assert_eq!(rest, "");
+    assert_eq!(parsed, Range::FromOpen("b"));
+}
+
+#[test]
+fn range_parses_to_open() {
+    let (rest, parsed) = range("..b").unwrap();
+    assert_eq!(rest, "");
+    assert_eq!(parsed, Range::ToOpen("b"));
+}
+
+#[test]
+fn range_parses_and_later() {
+    let (rest, parsed) = range("b+").unwrap();
+    assert_eq!(rest, "");
+    assert_eq!(parsed, Range::AndLater("b"));
+}
+
+#[test]
+fn range_resolves_closed() {
+    let stack = patches(&["a", "b", "c", "d"]);
+    let (_, parsed) = range("b..c").unwrap();
+    assert_eq!(names(&resolve_range(&parsed, &stack).unwrap()), vec!["b", "c"]);
+}
+
+#[test]
+fn range_resolves_inverted_as_error() {
+    let stack = patches(&["a", "b", "c", "d"]);
+    let (_, parsed) = range("c..a").unwrap();
+    assert!(matches!(
+        resolve_range(&parsed, &stack),
+        Err(RangeError::Inverted { .. })
+    ));
+}
+
+#[test]
+fn range_resolves_and_later() {
+    let stack = patches(&["a", "b", "c", "d"]);
+    let (_, parsed) = range("b+").unwrap();
+    assert_eq!(
+        names(&resolve_range(&parsed, &stack).unwrap()),
+        vec!["b", "c", "d"]
+    );
+}
+
+#[test]
+fn range_resolves_to_open() {
+    let stack = patches(&["a", "b", "c", "d"]);
+    let (_, parsed) = range("..c").unwrap();
+    assert_eq!(
+        names(&resolve_range(&parsed, &stack).unwrap()),
+        vec!["a", "b", "c"]
+    );
+}
+
+#[test]
+fn locator_resolves_down_by_default() {
+    let stack = patches(&["a", "b", "c", "d"]);
+    let (_, parsed) = locator("c~2").unwrap();
+    assert_eq!(resolve_locator(&parsed, &stack).unwrap().as_ref(), "a");
+}
+
+#[test]
+fn locator_negative_tilde_moves_up() {
+    let stack = patches(&["a", "b", "c", "d"]);
+    let (rest1, neg_tilde) = locator("b~-1").unwrap();
+    let (rest2, plus) = locator("b+1").unwrap();
+    assert_eq!(rest1, "");
+    assert_eq!(rest2, "");
+    assert_eq!(
+        resolve_locator(&neg_tilde, &stack).unwrap(),
+        resolve_locator(&plus, &stack).unwrap()
+    );
+    assert_eq!(resolve_locator(&neg_tilde, &stack).unwrap().as_ref(), "c");
+}
+
+#[test]
+fn locator_chained_operators_sum() {
+    let stack = patches(&["a", "b", "c", "d"]);
+    let (_, parsed) = locator("d~1~1").unwrap();
+    assert_eq!(resolve_locator(&parsed, &stack).unwrap().as_ref(), "b");
+}
+
+#[test]
+fn locator_out_of_range_is_an_error() {
+    let stack = patches(&["a", "b"]);
+    let (_, parsed) = locator("a~1").unwrap();
+    assert!(matches!(
+        resolve_locator(&parsed, &stack),
+        Err(LocatorError::OutOfRange { .. })
+    ));
+}